@@ -0,0 +1,69 @@
+//! Access control for message delivery: a pluggable policy consulted by
+//! [`Router::send`](crate::Router::send) and
+//! [`Router::publish`](crate::Router::publish) before a message reaches its
+//! receiver(s).
+
+use crate::Performative;
+use agentropic_core::AgentId;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// Decides whether `sender` may deliver a message with the given
+/// `performative` to `receiver`.
+pub trait AclPolicy: Send + Sync {
+    /// Return whether the send is allowed.
+    fn allow(&self, sender: AgentId, receiver: AgentId, performative: Performative) -> bool;
+}
+
+/// Default policy: every message is allowed. Keeps existing callers working
+/// unchanged until they opt into a stricter policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+impl AclPolicy for AllowAll {
+    fn allow(&self, _sender: AgentId, _receiver: AgentId, _performative: Performative) -> bool {
+        true
+    }
+}
+
+/// Policy that denies everything except explicitly permitted
+/// `(sender, receiver, performative)` combinations.
+#[derive(Debug, Default)]
+pub struct Allowlist {
+    rules: RwLock<HashMap<(AgentId, AgentId), HashSet<Performative>>>,
+}
+
+impl Allowlist {
+    /// Create an empty allowlist (denies everything until rules are added)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Permit `sender` to send `performative` to `receiver`.
+    pub fn permit(&self, sender: AgentId, receiver: AgentId, performative: Performative) {
+        self.rules
+            .write()
+            .unwrap()
+            .entry((sender, receiver))
+            .or_default()
+            .insert(performative);
+    }
+
+    /// Revoke a previously permitted combination.
+    pub fn revoke(&self, sender: AgentId, receiver: AgentId, performative: Performative) {
+        if let Some(performatives) = self.rules.write().unwrap().get_mut(&(sender, receiver)) {
+            performatives.remove(&performative);
+        }
+    }
+}
+
+impl AclPolicy for Allowlist {
+    fn allow(&self, sender: AgentId, receiver: AgentId, performative: Performative) -> bool {
+        self.rules
+            .read()
+            .unwrap()
+            .get(&(sender, receiver))
+            .map(|performatives| performatives.contains(&performative))
+            .unwrap_or(false)
+    }
+}
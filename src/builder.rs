@@ -1,5 +1,6 @@
 use crate::{Message, MessageId, Performative};
 use agentropic_core::AgentId;
+use std::time::Duration;
 
 /// Message builder
 #[derive(Debug)]
@@ -10,6 +11,7 @@ pub struct MessageBuilder {
     content: Option<String>,
     conversation_id: Option<String>,
     in_reply_to: Option<MessageId>,
+    ttl: Option<Duration>,
 }
 
 impl MessageBuilder {
@@ -22,6 +24,7 @@ impl MessageBuilder {
             content: None,
             conversation_id: None,
             in_reply_to: None,
+            ttl: None,
         }
     }
 
@@ -61,6 +64,12 @@ impl MessageBuilder {
         self
     }
 
+    /// Set a time-to-live after which the built message is considered expired
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
     /// Build the message
     pub fn build(self) -> Result<Message, crate::MessagingError> {
         let mut msg = Message::new(
@@ -75,6 +84,9 @@ impl MessageBuilder {
         if let Some(reply) = self.in_reply_to {
             msg = msg.with_reply_to(reply);
         }
+        if let Some(ttl) = self.ttl {
+            msg = msg.with_ttl(ttl);
+        }
         Ok(msg)
     }
 }
@@ -0,0 +1,91 @@
+//! Cluster federation: route messages to agents hosted on remote nodes.
+//!
+//! A single [`Router`](crate::Router) only knows about agents registered in
+//! its own process. Federation lets several routers, each running in its own
+//! process or machine, act as one logical system: when a message's receiver
+//! isn't local, [`Router::send`](crate::Router::send) consults
+//! [`ClusterMetadata`] to find the node that owns it and hands the message to
+//! a [`Transport`] for delivery.
+
+pub mod tcp;
+
+use crate::{Message, MessagingError};
+use agentropic_core::AgentId;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+pub use tcp::TcpTransport;
+
+/// Identifier for a node in a federated cluster of routers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeId(String);
+
+impl NodeId {
+    /// Create a new node ID
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl std::fmt::Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Maps agents to the cluster node that currently owns their mailbox.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterMetadata {
+    owners: Arc<RwLock<HashMap<AgentId, NodeId>>>,
+}
+
+impl ClusterMetadata {
+    /// Create empty cluster metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `agent_id` is hosted on `node`.
+    pub fn set_owner(&self, agent_id: AgentId, node: NodeId) {
+        self.owners.write().unwrap().insert(agent_id, node);
+    }
+
+    /// Remove an agent's ownership record, e.g. after it migrates or leaves.
+    pub fn remove_owner(&self, agent_id: &AgentId) {
+        self.owners.write().unwrap().remove(agent_id);
+    }
+
+    /// Look up which node owns `agent_id`, if any node has claimed it.
+    pub fn owner_of(&self, agent_id: &AgentId) -> Option<NodeId> {
+        self.owners.read().unwrap().get(agent_id).cloned()
+    }
+}
+
+/// Carries messages to and from other nodes in the cluster.
+///
+/// Implementations deliver outbound messages with [`send_remote`](Transport::send_remote)
+/// and surface inbound messages from other nodes one at a time through
+/// [`recv`](Transport::recv), mirroring [`Mailbox::receive`](crate::Mailbox::receive).
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send `message` to the node `node`.
+    async fn send_remote(&self, node: NodeId, message: Message) -> Result<(), MessagingError>;
+
+    /// Receive the next message that arrived from a remote node.
+    async fn recv(&self) -> Option<Message>;
+}
+
+/// A router's cluster membership: where to find remote agents and how to
+/// reach them.
+#[derive(Clone)]
+pub(crate) struct Federation {
+    pub(crate) metadata: ClusterMetadata,
+    pub(crate) transport: Arc<dyn Transport>,
+}
+
+impl std::fmt::Debug for Federation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Federation").finish_non_exhaustive()
+    }
+}
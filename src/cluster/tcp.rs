@@ -0,0 +1,84 @@
+use super::{NodeId, Transport};
+use crate::{Message, MessagingError};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+
+/// Default [`Transport`] for running a multi-process agent system over plain
+/// TCP, with each cluster message newline-delimited JSON on the wire.
+///
+/// `TcpTransport` listens for inbound connections from peer nodes and opens
+/// an outbound connection per send. It has no authentication or encryption
+/// of its own; pair it with a secured transport (e.g. one built on
+/// `transport::Transport`'s negotiated handshake) when running across
+/// untrusted networks.
+pub struct TcpTransport {
+    peers: HashMap<NodeId, SocketAddr>,
+    inbound: Mutex<mpsc::UnboundedReceiver<Message>>,
+}
+
+impl TcpTransport {
+    /// Bind a listener on `listen_addr` to accept connections from peer
+    /// nodes, and record `peers`' addresses for outbound sends.
+    pub async fn bind(
+        listen_addr: SocketAddr,
+        peers: HashMap<NodeId, SocketAddr>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(listen_addr).await?;
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let inbound_tx = inbound_tx.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(socket).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if let Ok(message) = serde_json::from_str::<Message>(&line) {
+                            if inbound_tx.send(message).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            peers,
+            inbound: Mutex::new(inbound_rx),
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    async fn send_remote(&self, node: NodeId, message: Message) -> Result<(), MessagingError> {
+        let addr = self
+            .peers
+            .get(&node)
+            .ok_or_else(|| MessagingError::Other(format!("unknown cluster node: {node}")))?;
+
+        let mut stream = TcpStream::connect(addr)
+            .await
+            .map_err(|e| MessagingError::Other(e.to_string()))?;
+
+        let mut payload =
+            serde_json::to_vec(&message).map_err(|e| MessagingError::Other(e.to_string()))?;
+        payload.push(b'\n');
+
+        stream
+            .write_all(&payload)
+            .await
+            .map_err(|e| MessagingError::Other(e.to_string()))
+    }
+
+    async fn recv(&self) -> Option<Message> {
+        self.inbound.lock().await.recv().await
+    }
+}
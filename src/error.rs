@@ -12,6 +12,14 @@ pub enum MessagingError {
     #[error("Lock error")]
     LockError,
 
+    /// Message's TTL elapsed before it could be delivered
+    #[error("Message expired")]
+    Expired,
+
+    /// ACL policy denied this send, or a registration failed its credential check
+    #[error("Unauthorized")]
+    Unauthorized,
+
     /// Other error
     #[error("Messaging error: {0}")]
     Other(String),
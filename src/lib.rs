@@ -3,7 +3,9 @@
 //#![warn(missing_docs)]
 #![allow(missing_docs)]
 
+pub mod acl;
 pub mod builder;
+pub mod cluster;
 pub mod error;
 pub mod mailbox;
 pub mod message;
@@ -11,11 +13,13 @@ pub mod performative;
 pub mod prelude;
 pub mod protocols;
 pub mod router;
+pub mod transport;
 
 // Re-exports
+pub use acl::{AclPolicy, AllowAll, Allowlist};
 pub use builder::MessageBuilder;
 pub use error::MessagingError;
-pub use mailbox::Mailbox;
+pub use mailbox::{Delivery, Mailbox};
 pub use message::{Message, MessageId};
 pub use performative::Performative;
 pub use router::Router;
@@ -1,11 +1,33 @@
 use crate::Message;
+use std::ops::Deref;
+use std::time::SystemTime;
 use tokio::sync::mpsc;
 
+/// A message paired with the instant it was handed to the broker's mailbox
+/// channel. Receivers can use `received_at` to sort or expire queued
+/// messages, since it's stamped at enqueue time by whatever delivered the
+/// message (e.g. [`Router::send`](crate::Router::send)) rather than at
+/// whenever the consumer happens to read it back out, and rather than
+/// trusting the sender's (possibly skewed) clock.
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub message: Message,
+    pub received_at: SystemTime,
+}
+
+impl Deref for Delivery {
+    type Target = Message;
+
+    fn deref(&self) -> &Message {
+        &self.message
+    }
+}
+
 /// Agent mailbox for receiving messages
 #[derive(Debug)]
 pub struct Mailbox {
-    receiver: mpsc::UnboundedReceiver<Message>,
-    sender: mpsc::UnboundedSender<Message>,
+    receiver: mpsc::UnboundedReceiver<Delivery>,
+    sender: mpsc::UnboundedSender<Delivery>,
 }
 
 impl Mailbox {
@@ -16,24 +38,30 @@ impl Mailbox {
     }
 
     /// Get sender handle
-    pub fn sender(&self) -> mpsc::UnboundedSender<Message> {
+    pub fn sender(&self) -> mpsc::UnboundedSender<Delivery> {
         self.sender.clone()
     }
 
-    /// Try to receive a message (non-blocking)
-    pub fn try_receive(&mut self) -> Option<Message> {
+    /// Try to receive a message (non-blocking), stamped with the instant it was enqueued
+    pub fn try_receive(&mut self) -> Option<Delivery> {
         self.receiver.try_recv().ok()
     }
 
-    /// Receive a message (async, awaits until available)
-    pub async fn receive(&mut self) -> Option<Message> {
+    /// Receive a message (async, awaits until available), stamped with the instant it was enqueued
+    pub async fn receive(&mut self) -> Option<Delivery> {
         self.receiver.recv().await
     }
 
-    /// Send a message to this mailbox
+    /// Send a message to this mailbox, stamping it with the current instant
+    /// — the authoritative receive time, captured here at enqueue rather
+    /// than whenever a consumer eventually calls `receive`/`try_receive`.
     pub fn send(&self, message: Message) -> Result<(), String> {
+        let delivery = Delivery {
+            message,
+            received_at: SystemTime::now(),
+        };
         self.sender
-            .send(message)
+            .send(delivery)
             .map_err(|e| format!("Failed to send message: {}", e))
     }
 }
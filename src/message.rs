@@ -1,7 +1,7 @@
 use crate::Performative;
 use agentropic_core::AgentId;
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use uuid::Uuid;
 
 /// Unique message identifier
@@ -38,6 +38,7 @@ pub struct Message {
     conversation_id: Option<String>,
     in_reply_to: Option<MessageId>,
     timestamp: SystemTime,
+    valid_until: Option<SystemTime>,
 }
 
 impl Message {
@@ -57,6 +58,7 @@ impl Message {
             conversation_id: None,
             in_reply_to: None,
             timestamp: SystemTime::now(),
+            valid_until: None,
         }
     }
 
@@ -100,6 +102,18 @@ impl Message {
         self.timestamp
     }
 
+    /// Get the deadline after which this message should be treated as stale
+    pub fn valid_until(&self) -> Option<SystemTime> {
+        self.valid_until
+    }
+
+    /// Whether this message's TTL has elapsed
+    pub fn is_expired(&self) -> bool {
+        self.valid_until
+            .map(|deadline| SystemTime::now() > deadline)
+            .unwrap_or(false)
+    }
+
     pub fn with_conversation_id(mut self, id: String) -> Self {
         self.conversation_id = Some(id);
         self
@@ -109,4 +123,10 @@ impl Message {
         self.in_reply_to = Some(id);
         self
     }
+
+    /// Set a time-to-live after which this message is considered expired
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.valid_until = Some(SystemTime::now() + ttl);
+        self
+    }
 }
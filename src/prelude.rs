@@ -1,10 +1,11 @@
 //! Prelude for convenient imports
 
+pub use crate::acl::{AclPolicy, AllowAll, Allowlist};
 pub use crate::message::{Message, MessageId};
 pub use crate::builder::MessageBuilder;
 pub use crate::performative::Performative;
 pub use crate::router::Router;
-pub use crate::mailbox::Mailbox;
+pub use crate::mailbox::{Delivery, Mailbox};
 pub use crate::error::MessagingError;
 
 // Re-export from core
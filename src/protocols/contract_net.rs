@@ -0,0 +1,126 @@
+use crate::{Message, MessageBuilder, MessageId, MessagingError, Performative, Router};
+use agentropic_core::AgentId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// FIPA Contract Net protocol helper for task allocation over
+/// `CFP`/`Propose`/`Accept`/`Reject`/`Refuse`.
+///
+/// Registers `initiator` with the router and spawns a background task that
+/// demultiplexes inbound messages by `conversation_id`, the same
+/// correlation-based approach [`protocols::RequestReply`](crate::protocols::RequestReply)
+/// uses to keep concurrent operations from cross-talking: each call to
+/// [`run`](ContractNet::run) gets its own `mpsc` channel keyed by a fresh
+/// conversation id, so multiple contract-net rounds in flight at once each
+/// only see their own replies.
+pub struct ContractNet {
+    router: Router,
+    initiator: AgentId,
+    rounds: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Message>>>>,
+}
+
+impl ContractNet {
+    /// Create a new ContractNet helper for `initiator`, registering it with
+    /// `router` and spawning the background demultiplexing task.
+    pub fn new(router: Router, initiator: AgentId) -> Result<Self, MessagingError> {
+        let mut inbound = router.register(initiator)?;
+        let rounds: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Message>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+
+        let demux_rounds = rounds.clone();
+        tokio::spawn(async move {
+            while let Some(delivery) = inbound.recv().await {
+                if let Some(conversation_id) = delivery.conversation_id() {
+                    if let Some(tx) = demux_rounds.lock().unwrap().get(conversation_id) {
+                        let _ = tx.send(delivery.message);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            router,
+            initiator,
+            rounds,
+        })
+    }
+
+    /// Get the agent ID this helper initiates rounds as
+    pub fn initiator(&self) -> AgentId {
+        self.initiator
+    }
+
+    /// Run one Contract Net round: send a `CFP` to every participant, collect
+    /// `Propose`/`Refuse` replies until `deadline` elapses, pick the best
+    /// bidder with `score`, notify the winner with `Accept` and every other
+    /// proposer with `Reject`, and return the winning proposal (or `None` if
+    /// everyone refused or nobody replied in time).
+    pub async fn run(
+        &self,
+        participants: &[AgentId],
+        task: impl Into<String>,
+        deadline: Duration,
+        score: impl Fn(&Message) -> Option<f64>,
+    ) -> Result<Option<Message>, MessagingError> {
+        let conversation_id = MessageId::new().to_string();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        self.rounds.lock().unwrap().insert(conversation_id.clone(), tx);
+
+        let task = task.into();
+        let mut cfp_ids = HashSet::new();
+        for participant in participants {
+            let cfp = MessageBuilder::new()
+                .sender(self.initiator)
+                .receiver(*participant)
+                .performative(Performative::CFP)
+                .content(task.clone())
+                .conversation_id(conversation_id.clone())
+                .build()?;
+            cfp_ids.insert(cfp.id());
+            self.router.send(cfp)?;
+        }
+
+        let mut proposals = Vec::new();
+        let deadline_at = tokio::time::Instant::now() + deadline;
+        while let Ok(Some(reply)) = tokio::time::timeout_at(deadline_at, rx.recv()).await {
+            let in_round = reply
+                .in_reply_to()
+                .map(|id| cfp_ids.contains(&id))
+                .unwrap_or(false);
+            if in_round && reply.performative() == Performative::Propose {
+                proposals.push(reply);
+            }
+        }
+
+        self.rounds.lock().unwrap().remove(&conversation_id);
+
+        let winner_id = proposals
+            .iter()
+            .filter_map(|proposal| score(proposal).map(|bid| (bid, proposal.id())))
+            .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, id)| id);
+
+        if let Some(winner_id) = winner_id {
+            for proposal in &proposals {
+                let performative = if proposal.id() == winner_id {
+                    Performative::Accept
+                } else {
+                    Performative::Reject
+                };
+                let reply = MessageBuilder::new()
+                    .sender(self.initiator)
+                    .receiver(proposal.sender())
+                    .performative(performative)
+                    .content("")
+                    .conversation_id(conversation_id.clone())
+                    .in_reply_to(proposal.id())
+                    .build()?;
+                self.router.send(reply)?;
+            }
+        }
+
+        Ok(proposals.into_iter().find(|proposal| Some(proposal.id()) == winner_id))
+    }
+}
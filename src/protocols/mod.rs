@@ -0,0 +1,7 @@
+//! Interaction protocols built on top of [`Router`](crate::Router).
+
+pub mod contract_net;
+pub mod request_reply;
+
+pub use contract_net::ContractNet;
+pub use request_reply::RequestReply;
@@ -1,22 +1,97 @@
-use crate::{Message, MessagingError, Router};
+use crate::{Message, MessageId, MessagingError, Router};
+use agentropic_core::AgentId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
 
-/// Request-Reply protocol helper
+/// Request-Reply protocol helper.
+///
+/// Registers `agent_id` with the router and spawns a background task that
+/// demultiplexes inbound messages by correlation id: a reply whose
+/// `in_reply_to` matches a pending `send_request` is handed to the waiting
+/// caller through a `oneshot` channel, giving synchronous-feeling RPC over
+/// the async router. Any other message (e.g. an incoming `Request` to
+/// answer) is queued and surfaced through [`recv`](RequestReply::recv) so
+/// normal traffic isn't lost.
 pub struct RequestReply {
     router: Router,
+    agent_id: AgentId,
     timeout: Duration,
+    pending: Arc<Mutex<HashMap<MessageId, oneshot::Sender<Message>>>>,
+    overflow: tokio::sync::Mutex<mpsc::UnboundedReceiver<Message>>,
 }
 
 impl RequestReply {
-    /// Create a new RequestReply protocol handler
-    pub fn new(router: Router, timeout: Duration) -> Self {
-        Self { router, timeout }
+    /// Create a new RequestReply protocol handler for `agent_id`, registering
+    /// it with `router` and spawning the background demultiplexing task.
+    pub fn new(router: Router, agent_id: AgentId, timeout: Duration) -> Result<Self, MessagingError> {
+        let mut inbound = router.register(agent_id)?;
+        let pending: Arc<Mutex<HashMap<MessageId, oneshot::Sender<Message>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let (overflow_tx, overflow_rx) = mpsc::unbounded_channel();
+
+        let demux_pending = pending.clone();
+        tokio::spawn(async move {
+            while let Some(delivery) = inbound.recv().await {
+                let waiter = delivery
+                    .in_reply_to()
+                    .and_then(|id| demux_pending.lock().unwrap().remove(&id));
+
+                match waiter {
+                    Some(tx) => {
+                        let _ = tx.send(delivery.message);
+                    }
+                    None => {
+                        if overflow_tx.send(delivery.message).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            router,
+            agent_id,
+            timeout,
+            pending,
+            overflow: tokio::sync::Mutex::new(overflow_rx),
+        })
+    }
+
+    /// Get the agent ID this protocol handler is registered as
+    pub fn agent_id(&self) -> AgentId {
+        self.agent_id
     }
 
-    /// Send a request and await a reply
+    /// Send a request and await a correlated reply, or time out.
+    ///
+    /// The replying agent builds its reply with `.with_reply_to(request.id())`
+    /// so it can be matched back to this call.
     pub async fn send_request(&self, msg: Message) -> Result<Message, MessagingError> {
-        // TODO: implement request-reply protocol
-        self.router.send(msg)?;
-        Err(MessagingError::Other("request-reply not yet implemented".into()))
+        let id = msg.id();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        if let Err(err) = self.router.send(msg) {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(err);
+        }
+
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(reply)) => Ok(reply),
+            Ok(Err(_)) => Err(MessagingError::Other("reply channel closed".into())),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                Err(MessagingError::Other("timeout".into()))
+            }
+        }
     }
-}
\ No newline at end of file
+
+    /// Receive the next message that isn't a correlated reply (e.g. an
+    /// incoming `Request` awaiting an answer).
+    pub async fn recv(&self) -> Option<Message> {
+        self.overflow.lock().await.recv().await
+    }
+}
@@ -1,13 +1,30 @@
+use crate::acl::{AclPolicy, AllowAll};
+use crate::cluster::{ClusterMetadata, Federation, Transport};
+use crate::mailbox::Delivery;
 use crate::{Message, MessagingError};
 use agentropic_core::AgentId;
-use std::collections::HashMap;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 use tokio::sync::mpsc;
 
 /// Message router
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Router {
-    senders: Arc<RwLock<HashMap<AgentId, mpsc::UnboundedSender<Message>>>>,
+    senders: Arc<RwLock<HashMap<AgentId, mpsc::UnboundedSender<Delivery>>>>,
+    topics: Arc<RwLock<HashMap<String, HashSet<AgentId>>>>,
+    federation: Arc<RwLock<Option<Federation>>>,
+    credentials: Arc<RwLock<HashMap<AgentId, String>>>,
+    acl: Arc<RwLock<Arc<dyn AclPolicy>>>,
+}
+
+impl std::fmt::Debug for Router {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Router").finish_non_exhaustive()
+    }
 }
 
 impl Router {
@@ -15,11 +32,37 @@ impl Router {
     pub fn new() -> Self {
         Self {
             senders: Arc::new(RwLock::new(HashMap::new())),
+            topics: Arc::new(RwLock::new(HashMap::new())),
+            federation: Arc::new(RwLock::new(None)),
+            credentials: Arc::new(RwLock::new(HashMap::new())),
+            acl: Arc::new(RwLock::new(Arc::new(AllowAll))),
         }
     }
 
-    /// Register an agent and return its mailbox receiver
-    pub fn register(&self, agent_id: AgentId) -> Result<mpsc::UnboundedReceiver<Message>, MessagingError> {
+    /// Register an agent and return its mailbox receiver.
+    ///
+    /// Refuses with `MessagingError::Unauthorized` if `agent_id` was
+    /// previously registered via [`register_with_credentials`](Router::register_with_credentials) —
+    /// otherwise this open path would let anyone reclaim a credentialed id
+    /// without the secret.
+    pub fn register(&self, agent_id: AgentId) -> Result<mpsc::UnboundedReceiver<Delivery>, MessagingError> {
+        if self
+            .credentials
+            .read()
+            .map_err(|_| MessagingError::LockError)?
+            .contains_key(&agent_id)
+        {
+            return Err(MessagingError::Unauthorized);
+        }
+
+        self.insert_sender(agent_id)
+    }
+
+    /// Create a mailbox channel for `agent_id` and store its sender,
+    /// overwriting any previous registration. Shared by `register` and
+    /// `register_with_credentials` once each has done its own
+    /// authorization check.
+    fn insert_sender(&self, agent_id: AgentId) -> Result<mpsc::UnboundedReceiver<Delivery>, MessagingError> {
         let (sender, receiver) = mpsc::unbounded_channel();
         let mut senders = self
             .senders
@@ -29,6 +72,60 @@ impl Router {
         Ok(receiver)
     }
 
+    /// Register (or reclaim) `agent_id`'s mailbox, authenticated by `secret`.
+    ///
+    /// The first registration for an `agent_id` stores a salted Argon2 hash
+    /// of `secret` — unless `agent_id` already has a live mailbox from a
+    /// plain [`register`](Router::register) call, in which case this fails
+    /// with `MessagingError::Unauthorized` rather than claiming it, since
+    /// that would let anyone lock an id out from under its current owner
+    /// just by being the first to attach credentials to it. Any later call
+    /// for the same id (e.g. a crashed agent reconnecting) must supply the
+    /// same secret to reclaim the mailbox, returning
+    /// `MessagingError::Unauthorized` otherwise — this stops an impostor
+    /// from hijacking someone else's id.
+    pub fn register_with_credentials(
+        &self,
+        agent_id: AgentId,
+        secret: &str,
+    ) -> Result<mpsc::UnboundedReceiver<Delivery>, MessagingError> {
+        let mut credentials = self
+            .credentials
+            .write()
+            .map_err(|_| MessagingError::LockError)?;
+
+        match credentials.get(&agent_id) {
+            Some(stored_hash) => {
+                let parsed =
+                    PasswordHash::new(stored_hash).map_err(|_| MessagingError::Unauthorized)?;
+                Argon2::default()
+                    .verify_password(secret.as_bytes(), &parsed)
+                    .map_err(|_| MessagingError::Unauthorized)?;
+            }
+            None => {
+                if self.is_registered(&agent_id) {
+                    return Err(MessagingError::Unauthorized);
+                }
+                let salt = SaltString::generate(&mut OsRng);
+                let hash = Argon2::default()
+                    .hash_password(secret.as_bytes(), &salt)
+                    .map_err(|_| MessagingError::Other("failed to hash credential".into()))?
+                    .to_string();
+                credentials.insert(agent_id, hash);
+            }
+        }
+        drop(credentials);
+
+        self.insert_sender(agent_id)
+    }
+
+    /// Replace this router's ACL policy, consulted by `send` and `publish`.
+    pub fn set_acl_policy(&self, policy: Arc<dyn AclPolicy>) -> Result<(), MessagingError> {
+        let mut acl = self.acl.write().map_err(|_| MessagingError::LockError)?;
+        *acl = policy;
+        Ok(())
+    }
+
     /// Unregister an agent
     pub fn unregister(&self, agent_id: &AgentId) -> Result<(), MessagingError> {
         let mut senders = self
@@ -39,8 +136,60 @@ impl Router {
         Ok(())
     }
 
-    /// Send a message to the receiver's mailbox
+    /// Send a message to the receiver's mailbox.
+    ///
+    /// If `message`'s receiver isn't registered locally and federation is
+    /// enabled via [`enable_federation`](Router::enable_federation), the
+    /// hand-off to the owning node runs on a spawned task, so this must be
+    /// called from within a Tokio runtime in that case — it returns
+    /// `MessagingError::Other` rather than panicking if there isn't one.
     pub fn send(&self, message: Message) -> Result<(), MessagingError> {
+        self.validate(&message)?;
+
+        let is_local = self
+            .senders
+            .read()
+            .map_err(|_| MessagingError::LockError)?
+            .contains_key(&message.receiver());
+
+        if is_local {
+            self.deliver_local(message)
+        } else {
+            self.deliver_remote(message)
+        }
+    }
+
+    /// Checks every send must pass, whether it originates locally via `send`
+    /// or arrives from another node via federation: the message hasn't
+    /// expired, and the ACL policy permits this sender/receiver/performative
+    /// combination. A federated peer delivering straight into `deliver_local`
+    /// without going through this would let it bypass both checks that a
+    /// local sender can't.
+    fn validate(&self, message: &Message) -> Result<(), MessagingError> {
+        if message.is_expired() {
+            return Err(MessagingError::Expired);
+        }
+        if !self.is_allowed(message.sender(), message.receiver(), message.performative())? {
+            return Err(MessagingError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn is_allowed(
+        &self,
+        sender: AgentId,
+        receiver: AgentId,
+        performative: crate::Performative,
+    ) -> Result<bool, MessagingError> {
+        let acl = self.acl.read().map_err(|_| MessagingError::LockError)?;
+        Ok(acl.allow(sender, receiver, performative))
+    }
+
+    /// Deliver to a locally-registered agent, stamping the instant it was
+    /// handed to the mailbox channel — the authoritative, broker-side
+    /// receive time, as opposed to whenever the consumer happens to read it
+    /// back out.
+    fn deliver_local(&self, message: Message) -> Result<(), MessagingError> {
         let senders = self
             .senders
             .read()
@@ -50,11 +199,83 @@ impl Router {
             .get(&message.receiver())
             .ok_or(MessagingError::AgentNotFound)?;
 
+        let delivery = Delivery {
+            message,
+            received_at: SystemTime::now(),
+        };
         sender
-            .send(message)
+            .send(delivery)
             .map_err(|e| MessagingError::SendFailed(e.to_string()))
     }
 
+    /// Hand off to the node that owns `message`'s receiver, if federation is
+    /// enabled and some node has claimed it.
+    ///
+    /// Requires a Tokio runtime context (the hand-off happens on a spawned
+    /// task, since `send` itself is synchronous); returns
+    /// `MessagingError::Other` instead of panicking if none is running.
+    fn deliver_remote(&self, message: Message) -> Result<(), MessagingError> {
+        let federation = self
+            .federation
+            .read()
+            .map_err(|_| MessagingError::LockError)?
+            .clone();
+
+        let Some(federation) = federation else {
+            return Err(MessagingError::AgentNotFound);
+        };
+
+        let Some(node) = federation.metadata.owner_of(&message.receiver()) else {
+            return Err(MessagingError::AgentNotFound);
+        };
+
+        let handle = tokio::runtime::Handle::try_current().map_err(|_| {
+            MessagingError::Other(
+                "sending to a federated agent requires a Tokio runtime context".into(),
+            )
+        })?;
+
+        let transport = federation.transport.clone();
+        handle.spawn(async move {
+            let _ = transport.send_remote(node, message).await;
+        });
+        Ok(())
+    }
+
+    /// Enable cluster federation: messages addressed to agents this router
+    /// doesn't host locally are looked up in `metadata` and forwarded over
+    /// `transport`. Also spawns a background task that feeds messages
+    /// arriving from the transport back into local delivery, subject to the
+    /// same expiry/ACL checks [`send`](Router::send) enforces — a remote
+    /// peer shouldn't be able to deliver traffic a local sender couldn't.
+    pub fn enable_federation(
+        &self,
+        metadata: ClusterMetadata,
+        transport: Arc<dyn Transport>,
+    ) -> Result<(), MessagingError> {
+        {
+            let mut federation = self
+                .federation
+                .write()
+                .map_err(|_| MessagingError::LockError)?;
+            *federation = Some(Federation {
+                metadata,
+                transport: transport.clone(),
+            });
+        }
+
+        let router = self.clone();
+        tokio::spawn(async move {
+            while let Some(message) = transport.recv().await {
+                if router.validate(&message).is_ok() {
+                    let _ = router.deliver_local(message);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
     /// Check if an agent is registered
     pub fn is_registered(&self, agent_id: &AgentId) -> bool {
         self.senders
@@ -62,10 +283,105 @@ impl Router {
             .map(|s| s.contains_key(agent_id))
             .unwrap_or(false)
     }
+
+    /// Subscribe an agent to a topic (or wildcard pattern)
+    pub fn subscribe(&self, topic: impl Into<String>, agent_id: AgentId) -> Result<(), MessagingError> {
+        let mut topics = self.topics.write().map_err(|_| MessagingError::LockError)?;
+        topics.entry(topic.into()).or_default().insert(agent_id);
+        Ok(())
+    }
+
+    /// Unsubscribe an agent from a topic
+    pub fn unsubscribe(&self, topic: &str, agent_id: &AgentId) -> Result<(), MessagingError> {
+        let mut topics = self.topics.write().map_err(|_| MessagingError::LockError)?;
+        if let Some(subscribers) = topics.get_mut(topic) {
+            subscribers.remove(agent_id);
+            if subscribers.is_empty() {
+                topics.remove(topic);
+            }
+        }
+        Ok(())
+    }
+
+    /// Publish a message to every agent subscribed to a topic pattern that
+    /// matches `topic`, skipping (and pruning) any subscriber that is no
+    /// longer registered. Returns the number of agents the message was
+    /// delivered to.
+    pub fn publish(&self, topic: &str, message: Message) -> Result<usize, MessagingError> {
+        if message.is_expired() {
+            return Err(MessagingError::Expired);
+        }
+        let matching: HashSet<AgentId> = {
+            let topics = self.topics.read().map_err(|_| MessagingError::LockError)?;
+            topics
+                .iter()
+                .filter(|(pattern, _)| topic_matches(pattern, topic))
+                .flat_map(|(_, subscribers)| subscribers.iter().copied())
+                .collect()
+        };
+
+        let mut dead = Vec::new();
+        let mut delivered = 0;
+        {
+            let senders = self.senders.read().map_err(|_| MessagingError::LockError)?;
+            for agent_id in &matching {
+                if !self.is_allowed(message.sender(), *agent_id, message.performative())? {
+                    continue;
+                }
+                let delivery = Delivery {
+                    message: message.clone(),
+                    received_at: SystemTime::now(),
+                };
+                match senders.get(agent_id) {
+                    Some(sender) if sender.send(delivery).is_ok() => delivered += 1,
+                    _ => dead.push(*agent_id),
+                }
+            }
+        }
+
+        if !dead.is_empty() {
+            let mut topics = self.topics.write().map_err(|_| MessagingError::LockError)?;
+            for subscribers in topics.values_mut() {
+                for agent_id in &dead {
+                    subscribers.remove(agent_id);
+                }
+            }
+            topics.retain(|_, subscribers| !subscribers.is_empty());
+        }
+
+        Ok(delivered)
+    }
 }
 
 impl Default for Router {
     fn default() -> Self {
         Self::new()
     }
+}
+
+/// Match a (possibly wildcard) subscription pattern against a concrete
+/// `.`-delimited topic, `*` standing in for exactly one segment and a
+/// trailing `>` matching one or more remaining segments. `>` is only
+/// meaningful as the pattern's last token; everywhere else it's matched
+/// literally.
+fn topic_matches(pattern: &str, topic: &str) -> bool {
+    let pattern_tokens: Vec<&str> = pattern.split('.').collect();
+    let topic_tokens: Vec<&str> = topic.split('.').collect();
+
+    let mut p = 0;
+    let mut t = 0;
+    while p < pattern_tokens.len() {
+        if pattern_tokens[p] == ">" && p == pattern_tokens.len() - 1 {
+            return t < topic_tokens.len();
+        }
+        if t >= topic_tokens.len() {
+            return false;
+        }
+        if pattern_tokens[p] != "*" && pattern_tokens[p] != topic_tokens[t] {
+            return false;
+        }
+        p += 1;
+        t += 1;
+    }
+    t == topic_tokens.len()
 }
\ No newline at end of file
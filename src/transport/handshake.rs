@@ -0,0 +1,259 @@
+//! Handshake negotiation: the client advertises the compression algorithms
+//! and cipher suites it supports in order of preference, the server picks
+//! the first of those it also supports and sends back its choice, and if a
+//! cipher other than `None` was chosen both sides perform an X25519 key
+//! agreement and derive a pair of
+//! directional keys (client-to-server, server-to-client) from the shared
+//! secret, so the two halves of the duplex connection never seal frames
+//! under the same (key, nonce) pair.
+
+use crate::MessagingError;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+const HANDSHAKE_VERSION: u16 = 1;
+
+/// A compression algorithm a peer can offer during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// Frames are sent as-is.
+    None,
+    /// Frames are compressed with a deflate-style codec.
+    Deflate,
+}
+
+/// A cipher suite a peer can offer for encrypting frames after the
+/// handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cipher {
+    /// Frames are sent in the clear.
+    None,
+    /// Frames are sealed with ChaCha20-Poly1305 using a key derived from an
+    /// X25519 key agreement performed during the handshake.
+    ChaCha20Poly1305,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Hello {
+    version: u16,
+    compressions: Vec<Compression>,
+    ciphers: Vec<Cipher>,
+    key_share: [u8; 32],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Welcome {
+    version: u16,
+    compression: Compression,
+    cipher: Cipher,
+    key_share: [u8; 32],
+}
+
+/// The negotiated modes for one connection, observable so callers can
+/// assert things like "encryption is on", plus the machinery to seal and
+/// open frames accordingly.
+pub struct NegotiatedSession {
+    pub(crate) compression: Compression,
+    pub(crate) cipher: Cipher,
+    send_key: Option<[u8; 32]>,
+    recv_key: Option<[u8; 32]>,
+    send_nonce: AtomicU64,
+    recv_nonce: AtomicU64,
+}
+
+impl NegotiatedSession {
+    fn new(
+        compression: Compression,
+        cipher: Cipher,
+        keys: Option<([u8; 32], [u8; 32])>,
+    ) -> Self {
+        let (send_key, recv_key) = match keys {
+            Some((send_key, recv_key)) => (Some(send_key), Some(recv_key)),
+            None => (None, None),
+        };
+        Self {
+            compression,
+            cipher,
+            send_key,
+            recv_key,
+            send_nonce: AtomicU64::new(0),
+            recv_nonce: AtomicU64::new(0),
+        }
+    }
+
+    /// The compression algorithm this connection negotiated.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// The cipher suite this connection negotiated. `Cipher::None` means
+    /// frames flow in the clear.
+    pub fn cipher(&self) -> Cipher {
+        self.cipher
+    }
+
+    pub(crate) fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, MessagingError> {
+        let Some(key) = self.send_key else {
+            return Ok(plaintext.to_vec());
+        };
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let nonce = nonce_for(self.send_nonce.fetch_add(1, Ordering::SeqCst));
+        cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| MessagingError::Other("failed to seal frame".into()))
+    }
+
+    pub(crate) fn open(&self, ciphertext: &[u8]) -> Result<Vec<u8>, MessagingError> {
+        let Some(key) = self.recv_key else {
+            return Ok(ciphertext.to_vec());
+        };
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let nonce = nonce_for(self.recv_nonce.fetch_add(1, Ordering::SeqCst));
+        cipher
+            .decrypt(&nonce, ciphertext)
+            .map_err(|_| MessagingError::Other("failed to open frame".into()))
+    }
+}
+
+fn nonce_for(counter: u64) -> Nonce {
+    let mut bytes = [0u8; 12];
+    bytes[4..].copy_from_slice(&counter.to_be_bytes());
+    *Nonce::from_slice(&bytes)
+}
+
+/// A label folded into the KDF so the two directions of a connection never
+/// share a key, even though both sides compute the same X25519 shared
+/// secret: with one key and both nonce counters starting at zero, the
+/// client's frame 0 and the server's frame 0 would be sealed under an
+/// identical (key, nonce) pair.
+#[derive(Clone, Copy)]
+enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl Direction {
+    fn label(self) -> &'static [u8] {
+        match self {
+            Direction::ClientToServer => b"agentropic-messaging c2s",
+            Direction::ServerToClient => b"agentropic-messaging s2c",
+        }
+    }
+}
+
+fn derive_key(shared: &[u8], direction: Direction) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(shared);
+    hasher.update(direction.label());
+    hasher.finalize().into()
+}
+
+fn derive_keys(secret: EphemeralSecret, peer_key: [u8; 32], we_are_client: bool) -> ([u8; 32], [u8; 32]) {
+    let shared = secret.diffie_hellman(&PublicKey::from(peer_key));
+    let (client_to_server, server_to_client) = (
+        derive_key(shared.as_bytes(), Direction::ClientToServer),
+        derive_key(shared.as_bytes(), Direction::ServerToClient),
+    );
+    if we_are_client {
+        (client_to_server, server_to_client)
+    } else {
+        (server_to_client, client_to_server)
+    }
+}
+
+async fn write_frame<S: AsyncWrite + Unpin>(io: &mut S, payload: &[u8]) -> Result<(), MessagingError> {
+    let len = payload.len() as u32;
+    io.write_all(&len.to_be_bytes())
+        .await
+        .map_err(|e| MessagingError::Other(e.to_string()))?;
+    io.write_all(payload)
+        .await
+        .map_err(|e| MessagingError::Other(e.to_string()))
+}
+
+async fn read_frame<S: AsyncRead + Unpin>(io: &mut S) -> Result<Vec<u8>, MessagingError> {
+    let mut len_buf = [0u8; 4];
+    io.read_exact(&mut len_buf)
+        .await
+        .map_err(|e| MessagingError::Other(e.to_string()))?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut payload = vec![0u8; len];
+    io.read_exact(&mut payload)
+        .await
+        .map_err(|e| MessagingError::Other(e.to_string()))?;
+    Ok(payload)
+}
+
+fn first_supported<T: Copy + PartialEq>(offered: &[T], supported: &[T]) -> Option<T> {
+    offered.iter().copied().find(|o| supported.contains(o))
+}
+
+/// The compression algorithms and ciphers this side of the handshake is
+/// willing to negotiate, most preferred first.
+fn supported_compressions() -> Vec<Compression> {
+    vec![Compression::Deflate, Compression::None]
+}
+
+fn supported_ciphers() -> Vec<Cipher> {
+    vec![Cipher::ChaCha20Poly1305, Cipher::None]
+}
+
+/// Perform the client side of the handshake: send our supported modes and
+/// an ephemeral key share, then read back what the server chose.
+pub(crate) async fn negotiate_client<S: AsyncRead + AsyncWrite + Unpin>(
+    io: &mut S,
+) -> Result<NegotiatedSession, MessagingError> {
+    let secret = EphemeralSecret::random_from_rng(OsRng);
+    let public = PublicKey::from(&secret);
+
+    let hello = Hello {
+        version: HANDSHAKE_VERSION,
+        compressions: supported_compressions(),
+        ciphers: supported_ciphers(),
+        key_share: public.to_bytes(),
+    };
+    write_frame(io, &serde_json::to_vec(&hello).map_err(|e| MessagingError::Other(e.to_string()))?).await?;
+
+    let welcome: Welcome = serde_json::from_slice(&read_frame(io).await?)
+        .map_err(|e| MessagingError::Other(e.to_string()))?;
+
+    let keys = (welcome.cipher != Cipher::None)
+        .then(|| derive_keys(secret, welcome.key_share, true));
+
+    Ok(NegotiatedSession::new(welcome.compression, welcome.cipher, keys))
+}
+
+/// Perform the server side of the handshake: read the peer's supported
+/// modes, pick the first mutually-supported compression and cipher, and
+/// send back our choice along with our own ephemeral key share.
+pub(crate) async fn negotiate_server<S: AsyncRead + AsyncWrite + Unpin>(
+    io: &mut S,
+) -> Result<NegotiatedSession, MessagingError> {
+    let hello: Hello = serde_json::from_slice(&read_frame(io).await?)
+        .map_err(|e| MessagingError::Other(e.to_string()))?;
+
+    let compression = first_supported(&hello.compressions, &supported_compressions())
+        .unwrap_or(Compression::None);
+    let cipher = first_supported(&hello.ciphers, &supported_ciphers()).unwrap_or(Cipher::None);
+
+    let secret = EphemeralSecret::random_from_rng(OsRng);
+    let public = PublicKey::from(&secret);
+
+    let welcome = Welcome {
+        version: HANDSHAKE_VERSION,
+        compression,
+        cipher,
+        key_share: public.to_bytes(),
+    };
+    write_frame(io, &serde_json::to_vec(&welcome).map_err(|e| MessagingError::Other(e.to_string()))?).await?;
+
+    let keys = (cipher != Cipher::None).then(|| derive_keys(secret, hello.key_share, false));
+
+    Ok(NegotiatedSession::new(compression, cipher, keys))
+}
@@ -0,0 +1,107 @@
+use agentropic_messaging::prelude::*;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn allowlist_denies_by_default_until_a_rule_is_permitted() {
+    let router = Router::new();
+    router.set_acl_policy(Arc::new(Allowlist::new())).unwrap();
+
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    router.register(receiver_id).unwrap();
+
+    let msg = Message::new(sender_id, receiver_id, Performative::Inform, "denied");
+    assert!(matches!(router.send(msg), Err(MessagingError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn allowlist_permits_only_the_granted_performative() {
+    let router = Router::new();
+    let acl = Arc::new(Allowlist::new());
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    acl.permit(sender_id, receiver_id, Performative::Inform);
+    router.set_acl_policy(acl).unwrap();
+
+    let mut receiver = router.register(receiver_id).unwrap();
+
+    let allowed = Message::new(sender_id, receiver_id, Performative::Inform, "ok");
+    router.send(allowed).unwrap();
+    assert_eq!(receiver.recv().await.unwrap().content(), "ok");
+
+    let denied = Message::new(sender_id, receiver_id, Performative::Request, "not ok");
+    assert!(matches!(router.send(denied), Err(MessagingError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn allowlist_revoke_removes_a_previously_granted_permission() {
+    let router = Router::new();
+    let acl = Arc::new(Allowlist::new());
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    acl.permit(sender_id, receiver_id, Performative::Inform);
+    acl.revoke(sender_id, receiver_id, Performative::Inform);
+    router.set_acl_policy(acl).unwrap();
+    router.register(receiver_id).unwrap();
+
+    let msg = Message::new(sender_id, receiver_id, Performative::Inform, "revoked");
+    assert!(matches!(router.send(msg), Err(MessagingError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn publish_skips_subscribers_the_acl_denies() {
+    let router = Router::new();
+    let acl = Arc::new(Allowlist::new());
+    let sender_id = AgentId::new();
+    let allowed_subscriber = AgentId::new();
+    let denied_subscriber = AgentId::new();
+    acl.permit(sender_id, allowed_subscriber, Performative::Inform);
+    router.set_acl_policy(acl).unwrap();
+
+    let mut allowed_receiver = router.register(allowed_subscriber).unwrap();
+    router.register(denied_subscriber).unwrap();
+    router.subscribe("news", allowed_subscriber).unwrap();
+    router.subscribe("news", denied_subscriber).unwrap();
+
+    let msg = Message::new(sender_id, allowed_subscriber, Performative::Inform, "bulletin");
+    assert_eq!(router.publish("news", msg).unwrap(), 1);
+    assert_eq!(allowed_receiver.recv().await.unwrap().content(), "bulletin");
+}
+
+#[tokio::test]
+async fn register_with_credentials_reclaims_with_the_right_secret() {
+    let router = Router::new();
+    let agent_id = AgentId::new();
+
+    router.register_with_credentials(agent_id, "s3cret").unwrap();
+    router.unregister(&agent_id).unwrap();
+
+    assert!(router.register_with_credentials(agent_id, "s3cret").is_ok());
+}
+
+#[tokio::test]
+async fn register_with_credentials_rejects_reconnect_with_the_wrong_secret() {
+    let router = Router::new();
+    let agent_id = AgentId::new();
+
+    router.register_with_credentials(agent_id, "s3cret").unwrap();
+    router.unregister(&agent_id).unwrap();
+
+    let err = router
+        .register_with_credentials(agent_id, "wrong")
+        .unwrap_err();
+    assert!(matches!(err, MessagingError::Unauthorized));
+}
+
+#[tokio::test]
+async fn register_with_credentials_cannot_hijack_a_live_plain_registration() {
+    let router = Router::new();
+    let agent_id = AgentId::new();
+
+    router.register(agent_id).unwrap();
+
+    let err = router
+        .register_with_credentials(agent_id, "s3cret")
+        .unwrap_err();
+    assert!(matches!(err, MessagingError::Unauthorized));
+}
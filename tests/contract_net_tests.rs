@@ -0,0 +1,79 @@
+use agentropic_messaging::prelude::*;
+use agentropic_messaging::protocols::ContractNet;
+use std::time::Duration;
+
+#[tokio::test]
+async fn highest_bidder_wins_over_a_lower_bid_and_a_refusal() {
+    let router = Router::new();
+    let initiator = ContractNet::new(router.clone(), AgentId::new()).unwrap();
+
+    let low_bidder = AgentId::new();
+    let high_bidder = AgentId::new();
+    let refuser = AgentId::new();
+
+    for (participant, reply_value) in [
+        (low_bidder, Some((Performative::Propose, "1.0"))),
+        (high_bidder, Some((Performative::Propose, "5.0"))),
+        (refuser, None),
+    ] {
+        let mut inbound = router.register(participant).unwrap();
+        let router = router.clone();
+        tokio::spawn(async move {
+            let cfp = inbound.recv().await.unwrap();
+            let reply = match reply_value {
+                Some((performative, bid)) => {
+                    Message::new(participant, cfp.sender(), performative, bid)
+                        .with_conversation_id(cfp.conversation_id().unwrap().to_string())
+                        .with_reply_to(cfp.id())
+                }
+                None => Message::new(participant, cfp.sender(), Performative::Refuse, "")
+                    .with_conversation_id(cfp.conversation_id().unwrap().to_string())
+                    .with_reply_to(cfp.id()),
+            };
+            router.send(reply).unwrap();
+
+            // Drain the initiator's follow-up Accept/Reject so the task
+            // doesn't leak, though this test doesn't assert on it directly.
+            let _ = tokio::time::timeout(Duration::from_millis(200), inbound.recv()).await;
+        });
+    }
+
+    let winner = initiator
+        .run(
+            &[low_bidder, high_bidder, refuser],
+            "paint the fence",
+            Duration::from_millis(200),
+            |proposal| proposal.content().parse::<f64>().ok(),
+        )
+        .await
+        .unwrap();
+
+    let winner = winner.unwrap();
+    assert_eq!(winner.sender(), high_bidder);
+    assert_eq!(winner.content(), "5.0");
+}
+
+#[tokio::test]
+async fn no_proposals_yields_no_winner() {
+    let router = Router::new();
+    let initiator = ContractNet::new(router.clone(), AgentId::new()).unwrap();
+    let participant = AgentId::new();
+
+    let mut inbound = router.register(participant).unwrap();
+    tokio::spawn(async move {
+        // Never reply — the round should time out with no proposals.
+        let _ = inbound.recv().await;
+    });
+
+    let winner = initiator
+        .run(
+            &[participant],
+            "unwanted task",
+            Duration::from_millis(50),
+            |proposal| proposal.content().parse::<f64>().ok(),
+        )
+        .await
+        .unwrap();
+
+    assert!(winner.is_none());
+}
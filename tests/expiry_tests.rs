@@ -0,0 +1,62 @@
+use agentropic_messaging::prelude::*;
+use std::time::Duration;
+
+#[tokio::test]
+async fn send_rejects_an_expired_message() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    router.register(receiver_id).unwrap();
+
+    let msg = Message::new(sender_id, receiver_id, Performative::Inform, "stale")
+        .with_ttl(Duration::from_millis(0));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    assert!(matches!(router.send(msg), Err(MessagingError::Expired)));
+}
+
+#[tokio::test]
+async fn send_delivers_a_message_whose_ttl_has_not_yet_elapsed() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    let mut receiver = router.register(receiver_id).unwrap();
+
+    let msg = Message::new(sender_id, receiver_id, Performative::Inform, "fresh")
+        .with_ttl(Duration::from_secs(60));
+    router.send(msg).unwrap();
+
+    assert_eq!(receiver.recv().await.unwrap().content(), "fresh");
+}
+
+#[tokio::test]
+async fn publish_also_rejects_an_expired_message() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let subscriber_id = AgentId::new();
+    router.register(subscriber_id).unwrap();
+    router.subscribe("news", subscriber_id).unwrap();
+
+    let msg = Message::new(sender_id, subscriber_id, Performative::Inform, "stale bulletin")
+        .with_ttl(Duration::from_millis(0));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    assert!(matches!(router.publish("news", msg), Err(MessagingError::Expired)));
+}
+
+#[tokio::test]
+async fn delivery_is_stamped_with_the_enqueue_instant_not_the_read_instant() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+    let mut receiver = router.register(receiver_id).unwrap();
+
+    let msg = Message::new(sender_id, receiver_id, Performative::Inform, "timed");
+    router.send(msg).unwrap();
+
+    let before_read = std::time::SystemTime::now();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let delivery = receiver.recv().await.unwrap();
+    assert!(delivery.received_at < before_read);
+}
@@ -0,0 +1,106 @@
+use agentropic_messaging::cluster::{ClusterMetadata, NodeId, Transport};
+use agentropic_messaging::prelude::*;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[derive(Default)]
+struct FakeTransport {
+    sent: Mutex<Vec<(NodeId, Message)>>,
+    inbound: Mutex<Option<mpsc::UnboundedReceiver<Message>>>,
+}
+
+impl FakeTransport {
+    fn new(inbound_rx: mpsc::UnboundedReceiver<Message>) -> Self {
+        Self {
+            sent: Mutex::new(Vec::new()),
+            inbound: Mutex::new(Some(inbound_rx)),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn send_remote(&self, node: NodeId, message: Message) -> Result<(), MessagingError> {
+        self.sent.lock().unwrap().push((node, message));
+        Ok(())
+    }
+
+    async fn recv(&self) -> Option<Message> {
+        let mut rx = self.inbound.lock().unwrap().take()?;
+        let message = rx.recv().await;
+        *self.inbound.lock().unwrap() = Some(rx);
+        message
+    }
+}
+
+#[tokio::test]
+async fn send_to_a_remote_agent_hands_off_to_the_owning_node() {
+    let router = Router::new();
+    let metadata = ClusterMetadata::new();
+    let (_inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+    let transport = std::sync::Arc::new(FakeTransport::new(inbound_rx));
+
+    let remote_agent = AgentId::new();
+    let node = NodeId::new("node-b");
+    metadata.set_owner(remote_agent, node.clone());
+    router.enable_federation(metadata, transport.clone()).unwrap();
+
+    let sender_id = AgentId::new();
+    let msg = Message::new(sender_id, remote_agent, Performative::Inform, "hello remote");
+    router.send(msg).unwrap();
+
+    // Give the fire-and-forget spawned hand-off a chance to run.
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let sent = transport.sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].0, node);
+    assert_eq!(sent[0].1.content(), "hello remote");
+}
+
+#[tokio::test]
+async fn inbound_federated_messages_are_still_subject_to_acl() {
+    let router = Router::new();
+    router
+        .set_acl_policy(std::sync::Arc::new(Allowlist::new()))
+        .unwrap();
+
+    let metadata = ClusterMetadata::new();
+    let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+    let transport = std::sync::Arc::new(FakeTransport::new(inbound_rx));
+    router.enable_federation(metadata, transport).unwrap();
+
+    let sender_id = AgentId::new();
+    let local_agent = AgentId::new();
+    let mut receiver = router.register(local_agent).unwrap();
+
+    let denied = Message::new(sender_id, local_agent, Performative::Inform, "from another node");
+    inbound_tx.send(denied).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn inbound_federated_messages_are_still_subject_to_expiry() {
+    let router = Router::new();
+
+    let metadata = ClusterMetadata::new();
+    let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+    let transport = std::sync::Arc::new(FakeTransport::new(inbound_rx));
+    router.enable_federation(metadata, transport).unwrap();
+
+    let sender_id = AgentId::new();
+    let local_agent = AgentId::new();
+    let mut receiver = router.register(local_agent).unwrap();
+
+    let expired = Message::new(sender_id, local_agent, Performative::Inform, "stale")
+        .with_ttl(Duration::from_millis(0));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    inbound_tx.send(expired).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,41 @@
+use agentropic_messaging::prelude::*;
+use agentropic_messaging::transport::{Cipher, Compression, Transport};
+
+#[tokio::test]
+async fn negotiates_encryption_and_round_trips_messages_both_ways() {
+    let (client_io, server_io) = tokio::io::duplex(4096);
+
+    let (client, server) = tokio::join!(Transport::connect(client_io), Transport::accept(server_io));
+    let mut client = client.unwrap();
+    let mut server = server.unwrap();
+
+    assert_eq!(client.negotiated().cipher(), Cipher::ChaCha20Poly1305);
+    assert_eq!(server.negotiated().cipher(), Cipher::ChaCha20Poly1305);
+    assert_eq!(client.negotiated().compression(), Compression::Deflate);
+    assert_eq!(server.negotiated().compression(), Compression::Deflate);
+
+    let sender_id = AgentId::new();
+    let receiver_id = AgentId::new();
+
+    let to_server = Message::new(sender_id, receiver_id, Performative::Inform, "client to server");
+    client.send(&to_server).await.unwrap();
+    let received = server.recv().await.unwrap().unwrap();
+    assert_eq!(received.content(), "client to server");
+
+    let to_client = Message::new(receiver_id, sender_id, Performative::Inform, "server to client");
+    server.send(&to_client).await.unwrap();
+    let received = client.recv().await.unwrap().unwrap();
+    assert_eq!(received.content(), "server to client");
+}
+
+#[tokio::test]
+async fn recv_returns_none_once_peer_closes_the_connection() {
+    let (client_io, server_io) = tokio::io::duplex(4096);
+
+    let (client, server) = tokio::join!(Transport::connect(client_io), Transport::accept(server_io));
+    let client = client.unwrap();
+    let mut server = server.unwrap();
+
+    drop(client);
+    assert!(server.recv().await.unwrap().is_none());
+}
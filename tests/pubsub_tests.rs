@@ -0,0 +1,51 @@
+use agentropic_messaging::prelude::*;
+
+#[tokio::test]
+async fn wildcard_trailing_gt_requires_at_least_one_more_segment() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let subscriber_id = AgentId::new();
+
+    let mut receiver = router.register(subscriber_id).unwrap();
+    router.subscribe("a.>", subscriber_id).unwrap();
+
+    let bare = Message::new(sender_id, subscriber_id, Performative::Inform, "bare");
+    assert_eq!(router.publish("a", bare).unwrap(), 0);
+
+    let nested = Message::new(sender_id, subscriber_id, Performative::Inform, "nested");
+    assert_eq!(router.publish("a.b.c", nested).unwrap(), 1);
+    assert_eq!(receiver.recv().await.unwrap().content(), "nested");
+}
+
+#[tokio::test]
+async fn single_star_matches_exactly_one_segment() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let subscriber_id = AgentId::new();
+
+    let _receiver = router.register(subscriber_id).unwrap();
+    router.subscribe("a.*", subscriber_id).unwrap();
+
+    let too_deep = Message::new(sender_id, subscriber_id, Performative::Inform, "deep");
+    assert_eq!(router.publish("a.b.c", too_deep).unwrap(), 0);
+
+    let one_segment = Message::new(sender_id, subscriber_id, Performative::Inform, "one");
+    assert_eq!(router.publish("a.b", one_segment).unwrap(), 1);
+}
+
+#[tokio::test]
+async fn publish_delivers_once_to_a_subscriber_matched_by_multiple_patterns() {
+    let router = Router::new();
+    let sender_id = AgentId::new();
+    let subscriber_id = AgentId::new();
+
+    let mut receiver = router.register(subscriber_id).unwrap();
+    router.subscribe("a.b", subscriber_id).unwrap();
+    router.subscribe("a.*", subscriber_id).unwrap();
+    router.subscribe("a.>", subscriber_id).unwrap();
+
+    let msg = Message::new(sender_id, subscriber_id, Performative::Inform, "once");
+    assert_eq!(router.publish("a.b", msg).unwrap(), 1);
+    assert_eq!(receiver.recv().await.unwrap().content(), "once");
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,53 @@
+use agentropic_messaging::prelude::*;
+use agentropic_messaging::protocols::RequestReply;
+use std::time::Duration;
+
+#[tokio::test]
+async fn send_request_times_out_when_nobody_replies() {
+    let router = Router::new();
+    let requester = RequestReply::new(router.clone(), AgentId::new(), Duration::from_millis(20)).unwrap();
+    let silent_receiver = AgentId::new();
+    let _silent_mailbox = router.register(silent_receiver).unwrap();
+
+    let request = Message::new(requester.agent_id(), silent_receiver, Performative::Request, "ping");
+    let err = requester.send_request(request).await.unwrap_err();
+    assert!(matches!(err, MessagingError::Other(_)));
+}
+
+#[tokio::test]
+async fn send_request_resolves_once_correlated_reply_arrives() {
+    let router = Router::new();
+    let requester = RequestReply::new(router.clone(), AgentId::new(), Duration::from_secs(1)).unwrap();
+    let responder_id = AgentId::new();
+    let mut responder = router.register(responder_id).unwrap();
+
+    let request = Message::new(requester.agent_id(), responder_id, Performative::Request, "ping");
+    let request_id = request.id();
+
+    let router_for_responder = router.clone();
+    let requester_id = requester.agent_id();
+    tokio::spawn(async move {
+        let delivery = responder.recv().await.unwrap();
+        let reply = Message::new(responder_id, requester_id, Performative::Inform, "pong")
+            .with_reply_to(delivery.id());
+        router_for_responder.send(reply).unwrap();
+    });
+
+    let reply = requester.send_request(request).await.unwrap();
+    assert_eq!(reply.content(), "pong");
+    assert_eq!(reply.in_reply_to(), Some(request_id));
+}
+
+#[tokio::test]
+async fn unsolicited_messages_surface_through_recv_instead_of_being_dropped() {
+    let router = Router::new();
+    let responder = RequestReply::new(router.clone(), AgentId::new(), Duration::from_millis(50)).unwrap();
+    let sender_id = AgentId::new();
+
+    let unsolicited = Message::new(sender_id, responder.agent_id(), Performative::Inform, "heads up");
+    router.send(unsolicited).unwrap();
+
+    let received = responder.recv().await.unwrap();
+    assert_eq!(received.content(), "heads up");
+}
+